@@ -0,0 +1,263 @@
+use crate::render::Renderer;
+use sexp::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LogicExp {
+    Variable(String),
+    Not(Box<LogicExp>),
+    And(Box<LogicExp>, Box<LogicExp>),
+    Or(Box<LogicExp>, Box<LogicExp>),
+    Implies(Box<LogicExp>, Box<LogicExp>),
+    Iff(Box<LogicExp>, Box<LogicExp>),
+}
+
+/// A parse failure, optionally pinpointing the offending byte range so the
+/// REPL can render a caret under the exact token that caused it. When `span`
+/// is `None` the REPL highlights the whole line instead; every error built
+/// with `Error::spanned`, which is how both front-ends report failures,
+/// carries a span.
+#[derive(Debug)]
+pub struct Error {
+    pub span: Option<Range<usize>>,
+    pub message: String,
+}
+
+impl Error {
+    pub fn spanned(span: Range<usize>, message: impl Into<String>) -> Self {
+        Error {
+            span: Some(span),
+            message: message.into(),
+        }
+    }
+}
+
+/// Best-effort byte span of `sexp` within `source`, found by round-tripping
+/// it through `Display` and searching for that text. Falls back to the whole
+/// source when the round-tripped text can't be found (e.g. it was quoted or
+/// reformatted on the way out).
+fn sub_span(source: &str, sexp: &Sexp) -> Range<usize> {
+    let text = sexp.to_string();
+    match source.find(&text) {
+        Some(start) => start..start + text.len(),
+        None => 0..source.len(),
+    }
+}
+
+impl LogicExp {
+    /// Parse an already-tokenized `Sexp` into a `LogicExp`. `source` is the
+    /// original text it was parsed from; since `sexp::Sexp` doesn't carry
+    /// byte positions, a failing sub-expression's span is recovered by
+    /// round-tripping it through `Display` and locating that text in
+    /// `source` — exact for the common case, and still an improvement over
+    /// underlining the whole line when two identical sub-expressions make it
+    /// ambiguous.
+    pub fn parse(sexp: Sexp, source: &str) -> Result<Self, Error> {
+        let not_sym = Sexp::Atom(Atom::S("-".to_string()));
+
+        match sexp {
+            Sexp::Atom(Atom::S(s)) => Ok(LogicExp::Variable(s)),
+            Sexp::List(arr) => match arr[..] {
+                [ref not, ref exp] if not.clone() == not_sym => {
+                    Ok(LogicExp::Not(Box::new(LogicExp::parse(exp.clone(), source)?)))
+                }
+                [ref exp0, ref sym, ref exp1] => {
+                    if let Sexp::Atom(Atom::S(s)) = sym {
+                        let e0 = Box::new(LogicExp::parse(exp0.clone(), source)?);
+                        let e1 = Box::new(LogicExp::parse(exp1.clone(), source)?);
+                        match &s[..] {
+                            "*" => Ok(LogicExp::And(e0, e1)),
+                            "+" => Ok(LogicExp::Or(e0, e1)),
+                            "=>" => Ok(LogicExp::Implies(e0, e1)),
+                            "<=>" => Ok(LogicExp::Iff(e0, e1)),
+                            _ => Err(Error::spanned(
+                                sub_span(source, sym),
+                                format!("unknown operator `{}`", s),
+                            )),
+                        }
+                    } else {
+                        Err(Error::spanned(sub_span(source, sym), "expected an operator symbol"))
+                    }
+                }
+                _ => {
+                    let whole = Sexp::List(arr);
+                    Err(Error::spanned(
+                        sub_span(source, &whole),
+                        "expected a unary or binary expression",
+                    ))
+                }
+            },
+            other => Err(Error::spanned(
+                sub_span(source, &other),
+                "expected a variable or a list",
+            )),
+        }
+    }
+
+    pub fn solve(&self, map: &HashMap<String, bool>) -> bool {
+        match self {
+            LogicExp::Variable(s) => map[s],
+            LogicExp::And(e0, e1) => e0.solve(map) & e1.solve(map),
+            LogicExp::Or(e0, e1) => e0.solve(map) | e1.solve(map),
+            LogicExp::Not(e) => !e.solve(map),
+            LogicExp::Implies(e0, e1) => (!e0.solve(map)) | e1.solve(map),
+            LogicExp::Iff(e0, e1) => {
+                ((!e0.solve(map)) | e1.solve(map)) & ((!e1.solve(map)) | e0.solve(map))
+            }
+        }
+    }
+
+    pub fn find_vars(&self) -> HashSet<String> {
+        match self {
+            LogicExp::Variable(s) => {
+                let mut set = HashSet::new();
+                set.insert(s.clone());
+                set
+            }
+            LogicExp::Not(e) => e.find_vars(),
+            LogicExp::And(e0, e1)
+            | LogicExp::Or(e0, e1)
+            | LogicExp::Implies(e0, e1)
+            | LogicExp::Iff(e0, e1) => e0.find_vars().union(&e1.find_vars()).cloned().collect(),
+        }
+    }
+
+    pub fn simple_table(&self, renderer: &dyn Renderer) -> String {
+        let mut vars: Vec<String> = self.find_vars().into_iter().collect();
+        // So that the order is consistent
+        vars.sort();
+        let num_vars = vars.len();
+        let num_iterations = (num_vars as f64).exp2() as usize;
+
+        let mut columns: Vec<LogicExp> = vars.iter().cloned().map(LogicExp::Variable).collect();
+        columns.push(self.clone());
+
+        let mut rows = Vec::with_capacity(num_iterations);
+        for i in 0..num_iterations {
+            let mut map = HashMap::new();
+            let mut num = i;
+            let mut row = Vec::with_capacity(columns.len());
+            for var in &vars {
+                let condition = num % 2 == 0;
+                map.insert(var.clone(), condition);
+                row.push(condition);
+                num /= 2;
+            }
+            row.push(self.solve(&map));
+            rows.push(row);
+        }
+
+        renderer.render(&columns, &rows)
+    }
+
+    pub fn get_steps(&self) -> Vec<Self> {
+        let mut prev = match self {
+            LogicExp::Variable(_) => Vec::new(),
+            LogicExp::Not(e) => e.get_steps(),
+            LogicExp::And(e0, e1)
+            | LogicExp::Or(e0, e1)
+            | LogicExp::Implies(e0, e1)
+            | LogicExp::Iff(e0, e1) => {
+                let mut v0 = e0.get_steps();
+                let v1 = e1.get_steps();
+                v1.into_iter().for_each(|x| {
+                    if !v0.contains(&x) {
+                        v0.push(x);
+                    }
+                });
+                v0
+            }
+        };
+        prev.push(self.clone());
+        prev
+    }
+
+    pub fn steps_table(&self, renderer: &dyn Renderer) -> String {
+        let steps = self.get_steps();
+        let mut vars: Vec<String> = self.find_vars().into_iter().collect();
+        vars.sort();
+
+        let num_vars = vars.len();
+        let num_iterations = (num_vars as f64).exp2() as usize;
+
+        let mut rows = Vec::with_capacity(num_iterations);
+        for i in 0..num_iterations {
+            let mut map = HashMap::new();
+            let mut num = i;
+            for var in &vars {
+                let condition = num % 2 == 0;
+                map.insert(var.clone(), condition);
+                num /= 2;
+            }
+            rows.push(steps.iter().map(|step| step.solve(&map)).collect());
+        }
+
+        renderer.render(&steps, &rows)
+    }
+
+    /// Evaluate this expression over every assignment of its variables and
+    /// report whether it's a tautology, a contradiction, or satisfiable —
+    /// along with a witnessing assignment in the satisfiable case.
+    pub fn classify(&self) -> Classification {
+        let mut vars: Vec<String> = self.find_vars().into_iter().collect();
+        vars.sort();
+        let num_vars = vars.len();
+        let num_iterations = (num_vars as f64).exp2() as usize;
+
+        let mut any_true = false;
+        let mut any_false = false;
+        let mut witness = None;
+
+        for i in 0..num_iterations {
+            let mut map = HashMap::new();
+            let mut num = i;
+            for var in &vars {
+                map.insert(var.clone(), num % 2 == 0);
+                num /= 2;
+            }
+            if self.solve(&map) {
+                any_true = true;
+                witness.get_or_insert_with(|| map.clone());
+            } else {
+                any_false = true;
+            }
+        }
+
+        match (any_true, any_false) {
+            (true, false) => Classification::Tautology,
+            (false, true) => Classification::Contradiction,
+            _ => Classification::Satisfiable(witness.unwrap()),
+        }
+    }
+}
+
+/// The result of evaluating an expression over all of its variables'
+/// assignments, as reported by `:classify`.
+#[derive(Debug)]
+pub enum Classification {
+    Tautology,
+    Contradiction,
+    Satisfiable(HashMap<String, bool>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_operator_points_at_the_operator_symbol() {
+        let source = "(a ^ b)";
+        let sexp = sexp::parse(source).unwrap();
+        let err = LogicExp::parse(sexp, source).unwrap_err();
+        assert_eq!(err.span, Some(source.find('^').unwrap()..source.find('^').unwrap() + 1));
+    }
+
+    #[test]
+    fn wrong_arity_points_at_the_sub_list() {
+        let source = "(a * b *)";
+        let sexp = sexp::parse(source).unwrap();
+        let err = LogicExp::parse(sexp, source).unwrap_err();
+        assert_eq!(err.span, Some(0..source.len()));
+    }
+}
@@ -0,0 +1,273 @@
+//! A precedence-climbing (Pratt) parser for conventional infix logic syntax,
+//! e.g. `A & B | !C => D <=> E`, producing the same `LogicExp` AST that the
+//! S-expression front-end builds.
+
+use crate::ast::{Error, LogicExp};
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Not,
+    And,
+    Or,
+    Implies,
+    Iff,
+    LParen,
+    RParen,
+}
+
+type Spanned<T> = (T, Range<usize>);
+
+fn tokenize(input: &str) -> Result<Vec<Spanned<Token>>, Error> {
+    // Spans are byte ranges into `input`, so track each char's byte offset
+    // alongside it rather than indexing into a `Vec<char>` by char count —
+    // the two diverge as soon as a non-ASCII character appears earlier in
+    // the line.
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let byte_at = |i: usize| chars.get(i).map_or(input.len(), |(b, _)| *b);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push((Token::LParen, start..start + 1));
+                i += 1;
+            }
+            ')' => {
+                tokens.push((Token::RParen, start..start + 1));
+                i += 1;
+            }
+            '!' | '~' => {
+                tokens.push((Token::Not, start..start + 1));
+                i += 1;
+            }
+            '&' | '*' => {
+                tokens.push((Token::And, start..start + 1));
+                i += 1;
+            }
+            '|' | '+' => {
+                tokens.push((Token::Or, start..start + 1));
+                i += 1;
+            }
+            '<' if chars.len() - i >= 3 && chars[i + 1].1 == '=' && chars[i + 2].1 == '>' => {
+                tokens.push((Token::Iff, start..byte_at(i + 3)));
+                i += 3;
+            }
+            '=' if chars.len() - i >= 2 && chars[i + 1].1 == '>' => {
+                tokens.push((Token::Implies, start..byte_at(i + 2)));
+                i += 2;
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let ident_start = i;
+                while i < chars.len() && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[ident_start..i].iter().map(|(_, c)| *c).collect();
+                tokens.push((Token::Ident(ident), start..byte_at(i)));
+            }
+            _ => {
+                return Err(Error::spanned(
+                    start..start + c.len_utf8(),
+                    format!("unexpected character `{}`", c),
+                ))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Left/right binding power for an infix operator. A right bp lower than the
+/// left bp makes the operator right-associative (the recursive call on the
+/// right re-admits an operator of the same precedence).
+fn infix_binding_power(op: &Token) -> Option<(u8, u8)> {
+    match op {
+        Token::And => Some((50, 51)),
+        Token::Or => Some((40, 41)),
+        Token::Implies => Some((30, 29)),
+        Token::Iff => Some((20, 19)),
+        _ => None,
+    }
+}
+
+const NOT_BINDING_POWER: u8 = 60;
+
+struct Parser {
+    tokens: Vec<Spanned<Token>>,
+    pos: usize,
+    /// End-of-input offset, used to point the caret somewhere sensible when
+    /// an expression is truncated rather than malformed.
+    eof: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Spanned<Token>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Spanned<Token>> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn eof_span(&self) -> Range<usize> {
+        self.eof..self.eof
+    }
+
+    fn parse_expr(&mut self, min_bp: u8) -> Result<LogicExp, Error> {
+        let mut lhs = match self.next() {
+            Some((Token::Ident(s), _)) => LogicExp::Variable(s),
+            Some((Token::Not, span)) => {
+                if self.peek().is_none() {
+                    return Err(Error::spanned(span, "expected an expression after `!`"));
+                }
+                let rhs = self.parse_expr(NOT_BINDING_POWER)?;
+                LogicExp::Not(Box::new(rhs))
+            }
+            Some((Token::LParen, span)) => {
+                let inner = self.parse_expr(0)?;
+                match self.next() {
+                    Some((Token::RParen, _)) => inner,
+                    Some((_, bad_span)) => {
+                        return Err(Error::spanned(bad_span, "expected `)`"))
+                    }
+                    None => return Err(Error::spanned(span, "unbalanced parentheses")),
+                }
+            }
+            Some((_, span)) => return Err(Error::spanned(span, "expected an expression")),
+            None => return Err(Error::spanned(self.eof_span(), "expected an expression")),
+        };
+
+        while let Some((op, span)) = self.peek() {
+            let (op, op_span) = (op.clone(), span.clone());
+            let (l_bp, r_bp) = match infix_binding_power(&op) {
+                Some(bp) => bp,
+                None => break,
+            };
+            if l_bp < min_bp {
+                break;
+            }
+            self.next();
+            if self.peek().is_none() {
+                return Err(Error::spanned(op_span, "expected an expression after operator"));
+            }
+            let rhs = self.parse_expr(r_bp)?;
+            lhs = match op {
+                Token::And => LogicExp::And(Box::new(lhs), Box::new(rhs)),
+                Token::Or => LogicExp::Or(Box::new(lhs), Box::new(rhs)),
+                Token::Implies => LogicExp::Implies(Box::new(lhs), Box::new(rhs)),
+                Token::Iff => LogicExp::Iff(Box::new(lhs), Box::new(rhs)),
+                _ => unreachable!(),
+            };
+        }
+
+        Ok(lhs)
+    }
+}
+
+/// Parse a line of infix logic syntax into a `LogicExp`.
+pub fn parse(input: &str) -> Result<LogicExp, Error> {
+    let tokens = tokenize(input)?;
+    let eof = input.len();
+    let mut parser = Parser { tokens, pos: 0, eof };
+    let exp = parser.parse_expr(0)?;
+    if parser.pos != parser.tokens.len() {
+        let (_, span) = &parser.tokens[parser.pos];
+        return Err(Error::spanned(span.clone(), "unexpected trailing input"));
+    }
+    Ok(exp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str) -> LogicExp {
+        LogicExp::Variable(name.to_string())
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            parse("A & B | C").unwrap(),
+            LogicExp::Or(
+                Box::new(LogicExp::And(Box::new(var("A")), Box::new(var("B")))),
+                Box::new(var("C")),
+            )
+        );
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and() {
+        assert_eq!(
+            parse("!A & B").unwrap(),
+            LogicExp::And(
+                Box::new(LogicExp::Not(Box::new(var("A")))),
+                Box::new(var("B")),
+            )
+        );
+    }
+
+    #[test]
+    fn implies_is_right_associative() {
+        assert_eq!(
+            parse("A => B => C").unwrap(),
+            LogicExp::Implies(
+                Box::new(var("A")),
+                Box::new(LogicExp::Implies(Box::new(var("B")), Box::new(var("C")))),
+            )
+        );
+    }
+
+    #[test]
+    fn iff_is_right_associative_and_lowest_precedence() {
+        assert_eq!(
+            parse("A <=> B <=> C").unwrap(),
+            LogicExp::Iff(
+                Box::new(var("A")),
+                Box::new(LogicExp::Iff(Box::new(var("B")), Box::new(var("C")))),
+            )
+        );
+        assert_eq!(
+            parse("A & B <=> C").unwrap(),
+            LogicExp::Iff(
+                Box::new(LogicExp::And(Box::new(var("A")), Box::new(var("B")))),
+                Box::new(var("C")),
+            )
+        );
+    }
+
+    #[test]
+    fn parens_reset_binding_power() {
+        assert_eq!(
+            parse("(A | B) & C").unwrap(),
+            LogicExp::And(
+                Box::new(LogicExp::Or(Box::new(var("A")), Box::new(var("B")))),
+                Box::new(var("C")),
+            )
+        );
+    }
+
+    #[test]
+    fn token_spans_are_byte_offsets_not_char_counts() {
+        // "Ω" is one char but two UTF-8 bytes, so the `@` after it sits at
+        // byte offset 3, not char offset 2.
+        let err = parse("Ω @ B").unwrap_err();
+        assert_eq!(err.span, Some(3..4));
+    }
+
+    #[test]
+    fn dangling_operator_reports_friendly_message() {
+        let err = parse("A &").unwrap_err();
+        assert_eq!(err.message, "expected an expression after operator");
+    }
+
+    #[test]
+    fn dangling_not_reports_friendly_message() {
+        let err = parse("!").unwrap_err();
+        assert_eq!(err.message, "expected an expression after `!`");
+    }
+}
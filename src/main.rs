@@ -1,225 +1,184 @@
+mod ast;
+mod infix;
+mod render;
+mod repl;
+
+use ast::{Classification, Error, LogicExp};
+use render::{CsvRenderer, LatexRenderer, Renderer, TextRenderer};
+use repl::ReplHelper;
 use rustyline::Editor;
-use sexp::*;
-use std::collections::{HashMap, HashSet};
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum LogicExp {
-    Variable(String),
-    Not(Box<LogicExp>),
-    And(Box<LogicExp>, Box<LogicExp>),
-    Or(Box<LogicExp>, Box<LogicExp>),
-    Implies(Box<LogicExp>, Box<LogicExp>),
-    Iff(Box<LogicExp>, Box<LogicExp>),
-}
-
-#[derive(Debug)]
-enum Error {
-    ParseError,
+use std::ops::Range;
+
+/// Which renderer `main` prints tables with. Starts as LaTeX and is flipped
+/// at runtime by the `:latex`/`:text`/`:csv` directives.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Latex,
+    Text,
+    Csv,
 }
 
-impl LogicExp {
-    fn parse(sexp: Sexp) -> Result<Self, Error> {
-        let not_sym = Sexp::Atom(Atom::S("-".to_string()));
-
-        match sexp {
-            Sexp::Atom(Atom::S(s)) => Ok(LogicExp::Variable(s)),
-            Sexp::List(arr) => match arr[..] {
-                [ref not, ref exp] if not.clone() == not_sym => {
-                    Ok(LogicExp::Not(Box::new(LogicExp::parse(exp.clone())?)))
-                }
-                [ref exp0, ref sym, ref exp1] => {
-                    if let Sexp::Atom(Atom::S(s)) = sym {
-                        let e0 = Box::new(LogicExp::parse(exp0.clone())?);
-                        let e1 = Box::new(LogicExp::parse(exp1.clone())?);
-                        match &s[..] {
-                            "*" => Ok(LogicExp::And(e0, e1)),
-                            "+" => Ok(LogicExp::Or(e0, e1)),
-                            "=>" => Ok(LogicExp::Implies(e0, e1)),
-                            "<=>" => Ok(LogicExp::Iff(e0, e1)),
-                            _ => Err(Error::ParseError),
-                        }
-                    } else {
-                        Err(Error::ParseError)
-                    }
-                }
-                _ => Err(Error::ParseError),
-            },
-            _ => Err(Error::ParseError),
-        }
-    }
-
-    fn solve(&self, map: &HashMap<String, bool>) -> bool {
+impl OutputFormat {
+    fn renderer(self) -> Box<dyn Renderer> {
         match self {
-            LogicExp::Variable(s) => map[s],
-            LogicExp::And(e0, e1) => e0.solve(map) & e1.solve(map),
-            LogicExp::Or(e0, e1) => e0.solve(map) | e1.solve(map),
-            LogicExp::Not(e) => !e.solve(map),
-            LogicExp::Implies(e0, e1) => (!e0.solve(map)) | e1.solve(map),
-            LogicExp::Iff(e0, e1) => {
-                ((!e0.solve(map)) | e1.solve(map)) & ((!e1.solve(map)) | e0.solve(map))
-            }
+            OutputFormat::Latex => Box::new(LatexRenderer),
+            OutputFormat::Text => Box::new(TextRenderer),
+            OutputFormat::Csv => Box::new(CsvRenderer),
         }
     }
+}
 
-    fn print_latex(&self) -> String {
-        match self {
-            LogicExp::Variable(s) => s.clone(),
-            LogicExp::Not(e) => format!("\\neg {}", e.print_latex()),
-            LogicExp::And(e0, e1) => format!("({} \\wedge {})", e0.print_latex(), e1.print_latex()),
-            LogicExp::Or(e0, e1) => format!("({} \\vee {})", e0.print_latex(), e1.print_latex()),
-            LogicExp::Implies(e0, e1) => {
-                format!("({} \\rightarrow {})", e0.print_latex(), e1.print_latex())
-            }
-            LogicExp::Iff(e0, e1) => format!("({} \\iff {})", e0.print_latex(), e1.print_latex()),
-        }
+fn parse_line(line: &str) -> Result<LogicExp, Error> {
+    if line.trim_start().starts_with('(') {
+        let sexp = sexp::parse(line)
+            .map_err(|e| Error::spanned(e.index..e.index + 1, e.message.to_string()))?;
+        LogicExp::parse(sexp, line)
+    } else {
+        infix::parse(line)
     }
+}
 
-    fn find_vars(&self) -> HashSet<String> {
-        match self {
-            LogicExp::Variable(s) => {
-                let mut set = HashSet::new();
-                set.insert(s.clone());
-                set
-            }
-            LogicExp::Not(e) => e.find_vars(),
-            LogicExp::And(e0, e1)
-            | LogicExp::Or(e0, e1)
-            | LogicExp::Implies(e0, e1)
-            | LogicExp::Iff(e0, e1) => e0.find_vars().union(&e1.find_vars()).cloned().collect(),
+/// Find which physical line of a (possibly multi-line) buffer a byte span
+/// falls on, and re-base the span to that line's own start — the span was
+/// computed against the whole buffer, so the raw offset would no longer line
+/// up with anything once the buffer spans more than one line.
+fn locate_span<'a>(buffer: &'a str, span: &Range<usize>) -> (&'a str, usize, usize) {
+    let mut line_start = 0;
+    let mut lines = buffer.split('\n').peekable();
+    while let Some(line) = lines.next() {
+        let line_end = line_start + line.len();
+        if span.start <= line_end || lines.peek().is_none() {
+            let start = span.start.saturating_sub(line_start).min(line.len());
+            let end = span
+                .end
+                .saturating_sub(line_start)
+                .max(start + 1)
+                .min(line.len() + 1);
+            return (line, start, end);
         }
+        line_start = line_end + 1; // account for the '\n' consumed by split
     }
+    ("", 0, 1)
+}
 
-    fn simple_table(&self) -> String {
-        let mut vars: Vec<String> = self.find_vars().into_iter().collect();
-        // So that the order is consistent
-        vars.sort();
-        let num_vars = vars.len();
-        let num_iterations = (num_vars as f64).exp2() as usize;
-        let mut s = String::new();
-
-        // Generate the headers
-        let mut fmt_s = String::from("|L|");
-        let mut header_s = String::new();
-        for var in &vars {
-            fmt_s.push_str("L|");
-            header_s.push_str(&format!(" {} &", var));
+/// Render a parse error the way `rustc` does: echo the offending line, draw a
+/// line of carets under the span that caused it, then print the message.
+fn report_error(buffer: &str, err: &Error) {
+    match &err.span {
+        Some(span) => {
+            let (line, start, end) = locate_span(buffer, span);
+            println!("{}", line);
+            println!("{}{}", " ".repeat(start), "^".repeat(end - start));
         }
-        header_s.push_str(&format!("{} \\\\\n\\hline\n", self.print_latex()));
-
-        for i in 0..num_iterations {
-            let mut map = HashMap::new();
-            let mut num = i;
-            for var in &vars {
-                let condition = num % 2 == 0;
-                map.insert(var.clone(), condition);
-                s.push_str(if condition { " T &" } else { " F &" });
-                num /= 2;
-            }
-            let solved = self.solve(&map);
-            s.push_str(if solved { " T \\\\\n" } else { " F \\\\\n" });
+        None => {
+            println!("{}", buffer);
+            let last_line_len = buffer.rsplit('\n').next().unwrap_or(buffer).len();
+            println!("{}", "^".repeat(last_line_len.max(1)));
         }
-        format!(
-            "\\begin{{tabular}}{{{}}}\n{}{}\\end{{tabular}}",
-            fmt_s, header_s, s
-        )
     }
+    println!("{}", err.message);
+}
 
-    fn get_steps(&self) -> Vec<Self> {
-        let mut prev = match self {
-            LogicExp::Variable(_) => Vec::new(),
-            LogicExp::Not(e) => e.get_steps(),
-            LogicExp::And(e0, e1)
-            | LogicExp::Or(e0, e1)
-            | LogicExp::Implies(e0, e1)
-            | LogicExp::Iff(e0, e1) => {
-                let mut v0 = e0.get_steps();
-                let v1 = e1.get_steps();
-                v1.into_iter().for_each(|x| {
-                    if !v0.contains(&x) {
-                        v0.push(x);
-                    }
-                });
-                v0
-            }
-        };
-        prev.push(self.clone());
-        prev
-    }
-
-    fn steps_table(&self) -> String {
-        let steps = self.get_steps();
-        let mut vars: Vec<String> = self.find_vars().into_iter().collect();
-        vars.sort();
-
-        let num_vars = vars.len();
-        let num_iterations = (num_vars as f64).exp2() as usize;
-        let mut s = String::new();
+fn print_table(lexp: &LogicExp, steps: bool, format: OutputFormat) {
+    let renderer = format.renderer();
+    let table = if steps {
+        lexp.steps_table(renderer.as_ref())
+    } else {
+        lexp.simple_table(renderer.as_ref())
+    };
+    println!("{}", table);
+}
 
-        // Generate the headers
-        let mut fmt_s = String::from("|");
-        let mut header_s = String::from("\\hline\n");
-        for (i, step) in steps.iter().enumerate() {
-            fmt_s.push_str("c|");
-            header_s.push_str(&step.print_latex());
-            header_s.push_str(if i == (steps.len() - 1) {
-                "\\\\\n\\hline\n"
-            } else {
-                "&"
-            });
+fn print_classification(lexp: &LogicExp) {
+    match lexp.classify() {
+        Classification::Tautology => println!("tautology (true under every assignment)"),
+        Classification::Contradiction => println!("contradiction (false under every assignment)"),
+        Classification::Satisfiable(witness) => {
+            let mut vars: Vec<&String> = witness.keys().collect();
+            vars.sort();
+            let assignment = vars
+                .iter()
+                .map(|v| format!("{} = {}", v, witness[*v]))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("satisfiable (neither a tautology nor a contradiction)");
+            println!("witness: {}", assignment);
         }
+    }
+}
 
-        for i in 0..num_iterations {
-            let mut map = HashMap::new();
-            let mut num = i;
-            for var in &vars {
-                let condition = num % 2 == 0;
-                map.insert(var.clone(), condition);
-                num /= 2;
-            }
-            for (i, step) in steps.iter().enumerate() {
-                let solved = step.solve(&map);
-                s.push_str(if solved { " T " } else { " F " });
-                s.push_str(if i == (steps.len() - 1) {
-                    "\\\\\n\\hline\n"
-                } else {
-                    "&"
-                });
+/// Dispatch a `:`-prefixed meta-command. Returns `false` if `command` wasn't
+/// recognized, so the caller can fall back to treating the line as an
+/// expression.
+fn run_command(command: &str, steps: &mut bool, format: &mut OutputFormat) -> bool {
+    let command = command.trim();
+    match command {
+        ":steps" => {
+            *steps = true;
+            true
+        }
+        ":nosteps" => {
+            *steps = false;
+            true
+        }
+        ":latex" => {
+            *format = OutputFormat::Latex;
+            true
+        }
+        ":text" => {
+            *format = OutputFormat::Text;
+            true
+        }
+        ":csv" => {
+            *format = OutputFormat::Csv;
+            true
+        }
+        _ if command.starts_with(":classify ") => {
+            let expr = &command[":classify ".len()..];
+            match parse_line(expr) {
+                Ok(lexp) => print_classification(&lexp),
+                Err(e) => report_error(expr, &e),
             }
+            true
         }
-        format!(
-            "\\begin{{tabular}}{{{}}}\n{}{}\\end{{tabular}}",
-            fmt_s, header_s, s
-        )
+        _ => false,
     }
 }
 
 fn main() {
-    let no_steps = std::env::args().last() == Some(String::from("--no-steps"));
-    let mut rl = Editor::<()>::new();
+    let mut steps = std::env::args().next_back() != Some(String::from("--no-steps"));
+    let mut format = OutputFormat::Latex;
+    let mut rl = Editor::<ReplHelper>::new();
+    rl.set_helper(Some(ReplHelper));
     loop {
         let line = rl.readline(">> ");
         if line.is_err() {
             break;
         };
         let line = line.unwrap();
-        let sexp = sexp::parse(&line);
-        let sexp = match sexp {
-            Ok(s) => s,
+        if line.trim_start().starts_with(':')
+            && run_command(line.trim(), &mut steps, &mut format)
+        {
+            continue;
+        }
+        let lexp = match parse_line(&line) {
+            Ok(lexp) => lexp,
             Err(e) => {
-                println!("{:?}", e);
+                report_error(&line, &e);
                 continue;
             }
         };
-        let lexp = LogicExp::parse(sexp);
-        if lexp.is_err() {
-            println!("can't parse line");
-            continue;
-        }
-        let lexp = lexp.unwrap();
-        if no_steps {
-            println!("{}", lexp.simple_table());
-        } else {
-            println!("{}", lexp.steps_table());
-        }
+        print_table(&lexp, steps, format);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_sexp_error_carries_a_span() {
+        let err = parse_line("(* A B").unwrap_err();
+        assert!(err.span.is_some(), "expected a precise span, got {:?}", err.span);
     }
 }
@@ -0,0 +1,191 @@
+//! Table renderers. `LogicExp::simple_table`/`steps_table` (in `ast.rs`)
+//! enumerate the columns (variables, plus either the whole expression or
+//! each sub-step) and every row of the truth table, then hand them to a
+//! `Renderer` to turn into LaTeX, a terminal-friendly Unicode table, or CSV.
+//! The enumeration logic lives in one place; only the formatting varies.
+
+use crate::ast::LogicExp;
+
+pub trait Renderer {
+    /// Format a column header: a variable name or a sub-expression.
+    fn format_exp(&self, exp: &LogicExp) -> String;
+
+    /// Format a single boolean cell.
+    fn cell(&self, value: bool) -> String;
+
+    /// Assemble a header row from already-formatted column labels.
+    fn header_row(&self, columns: &[String]) -> String;
+
+    /// Assemble a single data row from already-formatted cells.
+    fn data_row(&self, cells: &[String]) -> String;
+
+    /// Anything that needs to follow the last data row (e.g. closing a
+    /// LaTeX environment). Most renderers don't need one.
+    fn footer(&self, _num_columns: usize) -> String {
+        String::new()
+    }
+
+    /// Build the full table from the column expressions and every row of
+    /// boolean values. The default composes `header_row`/`data_row`/`cell`,
+    /// which is enough for every renderer below except `TextRenderer`, which
+    /// overrides it to size its columns to their contents.
+    fn render(&self, columns: &[LogicExp], rows: &[Vec<bool>]) -> String {
+        let labels: Vec<String> = columns.iter().map(|c| self.format_exp(c)).collect();
+        let mut out = self.header_row(&labels);
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|v| self.cell(*v)).collect();
+            out.push_str(&self.data_row(&cells));
+        }
+        out.push_str(&self.footer(labels.len()));
+        out
+    }
+}
+
+fn format_exp_latex(exp: &LogicExp) -> String {
+    match exp {
+        LogicExp::Variable(s) => s.clone(),
+        LogicExp::Not(e) => format!("\\neg {}", format_exp_latex(e)),
+        LogicExp::And(e0, e1) => {
+            format!("({} \\wedge {})", format_exp_latex(e0), format_exp_latex(e1))
+        }
+        LogicExp::Or(e0, e1) => format!("({} \\vee {})", format_exp_latex(e0), format_exp_latex(e1)),
+        LogicExp::Implies(e0, e1) => format!(
+            "({} \\rightarrow {})",
+            format_exp_latex(e0),
+            format_exp_latex(e1)
+        ),
+        LogicExp::Iff(e0, e1) => format!("({} \\iff {})", format_exp_latex(e0), format_exp_latex(e1)),
+    }
+}
+
+fn format_exp_text(exp: &LogicExp) -> String {
+    match exp {
+        LogicExp::Variable(s) => s.clone(),
+        LogicExp::Not(e) => format!("!{}", format_exp_text(e)),
+        LogicExp::And(e0, e1) => format!("({} & {})", format_exp_text(e0), format_exp_text(e1)),
+        LogicExp::Or(e0, e1) => format!("({} | {})", format_exp_text(e0), format_exp_text(e1)),
+        LogicExp::Implies(e0, e1) => format!("({} => {})", format_exp_text(e0), format_exp_text(e1)),
+        LogicExp::Iff(e0, e1) => format!("({} <=> {})", format_exp_text(e0), format_exp_text(e1)),
+    }
+}
+
+/// Renders a LaTeX `tabular` environment, as pasted into a document.
+pub struct LatexRenderer;
+
+impl Renderer for LatexRenderer {
+    fn format_exp(&self, exp: &LogicExp) -> String {
+        format_exp_latex(exp)
+    }
+
+    fn cell(&self, value: bool) -> String {
+        if value { " T ".to_string() } else { " F ".to_string() }
+    }
+
+    fn header_row(&self, columns: &[String]) -> String {
+        let col_spec = "|".to_string() + &"c|".repeat(columns.len());
+        format!(
+            "\\begin{{tabular}}{{{}}}\n\\hline\n{} \\\\\n\\hline\n",
+            col_spec,
+            columns.join(" & ")
+        )
+    }
+
+    fn data_row(&self, cells: &[String]) -> String {
+        format!("{}\\\\\n\\hline\n", cells.join("&"))
+    }
+
+    fn footer(&self, _num_columns: usize) -> String {
+        "\\end{tabular}".to_string()
+    }
+}
+
+/// Renders a Unicode box-drawing table for direct viewing in a terminal.
+pub struct TextRenderer;
+
+impl TextRenderer {
+    fn rule(widths: &[usize], left: &str, mid: &str, right: &str, fill: char) -> String {
+        let segments: Vec<String> = widths
+            .iter()
+            .map(|w| fill.to_string().repeat(w + 2))
+            .collect();
+        format!("{}{}{}\n", left, segments.join(mid), right)
+    }
+
+    fn row(cells: &[String], widths: &[usize]) -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(c, w)| format!(" {:^width$} ", c, width = w))
+            .collect();
+        format!("│{}│\n", padded.join("│"))
+    }
+}
+
+impl Renderer for TextRenderer {
+    fn format_exp(&self, exp: &LogicExp) -> String {
+        format_exp_text(exp)
+    }
+
+    fn cell(&self, value: bool) -> String {
+        if value { "T".to_string() } else { "F".to_string() }
+    }
+
+    fn header_row(&self, columns: &[String]) -> String {
+        // Column widths aren't known yet since the data rows haven't been
+        // seen; `render` is overridden below so this is never called.
+        columns.join(" | ")
+    }
+
+    fn data_row(&self, cells: &[String]) -> String {
+        cells.join(" | ")
+    }
+
+    fn render(&self, columns: &[LogicExp], rows: &[Vec<bool>]) -> String {
+        let labels: Vec<String> = columns.iter().map(|c| self.format_exp(c)).collect();
+        let formatted_rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.iter().map(|v| self.cell(*v)).collect())
+            .collect();
+
+        let widths: Vec<usize> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                formatted_rows
+                    .iter()
+                    .map(|row| row[i].len())
+                    .fold(label.len(), usize::max)
+            })
+            .collect();
+
+        let mut out = Self::rule(&widths, "┌", "┬", "┐", '─');
+        out.push_str(&Self::row(&labels, &widths));
+        out.push_str(&Self::rule(&widths, "├", "┼", "┤", '─'));
+        for row in &formatted_rows {
+            out.push_str(&Self::row(row, &widths));
+        }
+        out.push_str(&Self::rule(&widths, "└", "┴", "┘", '─'));
+        out
+    }
+}
+
+/// Renders comma-separated values, for spreadsheets or pasting into docs.
+pub struct CsvRenderer;
+
+impl Renderer for CsvRenderer {
+    fn format_exp(&self, exp: &LogicExp) -> String {
+        format_exp_text(exp)
+    }
+
+    fn cell(&self, value: bool) -> String {
+        if value { "T".to_string() } else { "F".to_string() }
+    }
+
+    fn header_row(&self, columns: &[String]) -> String {
+        format!("{}\n", columns.join(","))
+    }
+
+    fn data_row(&self, cells: &[String]) -> String {
+        format!("{}\n", cells.join(","))
+    }
+}
@@ -0,0 +1,64 @@
+//! A bracket-aware rustyline validator. It keeps the editor in `Incomplete`
+//! state until parentheses balance and, for the infix front-end, the line
+//! doesn't end in a dangling binary operator, letting the REPL accept a long
+//! or nested expression typed across several lines with a `.. ` continuation
+//! prompt instead of erroring out on the first `\n`.
+
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::Helper;
+
+#[derive(Default)]
+pub struct ReplHelper;
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let input = ctx.input();
+        if unbalanced_parens(input) || ends_with_binary_operator(input) {
+            return Ok(ValidationResult::Incomplete);
+        }
+        Ok(ValidationResult::Valid(None))
+    }
+}
+
+fn unbalanced_parens(input: &str) -> bool {
+    let mut depth = 0i32;
+    for c in input.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+/// A trailing `&`, `|`, `=>`, `<=>`, etc. means the infix expression clearly
+/// isn't finished yet, so keep reading more lines rather than handing a
+/// truncated expression to the parser.
+fn ends_with_binary_operator(input: &str) -> bool {
+    let trimmed = input.trim_end();
+    if trimmed.is_empty() || trimmed.starts_with('(') {
+        return false;
+    }
+    trimmed.ends_with("<=>")
+        || trimmed.ends_with("=>")
+        || trimmed.ends_with('&')
+        || trimmed.ends_with('*')
+        || trimmed.ends_with('|')
+        || trimmed.ends_with('+')
+}